@@ -3,7 +3,7 @@ use std::io::{self, prelude::*, BufWriter};
 use std::path::PathBuf;
 use std::process;
 
-use jpeg_to_pdf::JpegToPdf;
+use jpeg_to_pdf::{Anchor, FitPolicy, JpegToPdf, MediaBox, PageLayout, PageOrientation};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -18,10 +18,31 @@ struct Opt {
     #[structopt(long, default_value = "300")]
     dpi: f64,
 
+    /// Derive each page's size from the image's own EXIF/JFIF resolution instead of --dpi
+    #[structopt(long)]
+    use_image_dpi: bool,
+
     /// Strip EXIF metadata from the embedded images
     #[structopt(long)]
     strip_exif: bool,
 
+    /// Populate the PDF's title/author/producer/keywords/dates from the first image's EXIF data
+    #[structopt(long)]
+    import_metadata: bool,
+
+    /// Downscale embedded JPEGs so neither dimension exceeds this many pixels
+    #[structopt(long)]
+    max_dimension: Option<u32>,
+
+    /// Re-encode embedded JPEGs at this JPEG quality (0-100) to cap output file size
+    #[structopt(long)]
+    recompress_quality: Option<u8>,
+
+    /// Fit every page to a fixed paper size ("a4" or "letter") instead of sizing it from each
+    /// image, centering the image and adding margins as needed
+    #[structopt(long)]
+    page_size: Option<String>,
+
     /// Add a title to the generated PDF
     #[structopt(long)]
     title: Option<String>,
@@ -35,6 +56,22 @@ fn main() -> io::Result<()> {
         process::exit(-1);
     }
 
+    let page_layout = match opt.page_size.as_deref() {
+        None => None,
+        Some("a4") => Some(MediaBox::A4),
+        Some("letter") => Some(MediaBox::Letter),
+        Some(other) => {
+            eprintln!("Unknown --page-size \"{}\"; expected \"a4\" or \"letter\"", other);
+            process::exit(-1);
+        }
+    }
+    .map(|media_box| PageLayout {
+        media_box,
+        orientation: PageOrientation::Auto,
+        fit: FitPolicy::Contain,
+        anchor: Anchor::Center,
+    });
+
     let out_file = File::create(match opt.output {
         Some(p) => p,
         None => {
@@ -51,7 +88,12 @@ fn main() -> io::Result<()> {
     }
     job = job
         .set_dpi(opt.dpi)
+        .use_image_dpi(opt.use_image_dpi)
         .strip_exif(opt.strip_exif)
+        .import_metadata(opt.import_metadata)
+        .max_dimension(opt.max_dimension)
+        .recompress_quality(opt.recompress_quality)
+        .page_layout(page_layout)
         .set_document_title(opt.title.unwrap_or_else(String::new));
 
     let mut out = BufWriter::new(out_file);
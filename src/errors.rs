@@ -28,6 +28,12 @@ pub enum Cause {
     UnexpectedImageInfo,
     ImageSections(img_parts::Error),
     PdfWrite(printpdf::errors::Error),
+    UnknownFormat,
+    ImageCodec(image::ImageError),
+    ImageCompress(std::io::Error),
+    Jpeg2000Header,
+    TiffDecode(tiff::TiffError),
+    UnsupportedTiffFrame,
 }
 
 impl Display for Cause {
@@ -40,6 +46,20 @@ impl Display for Cause {
             }
             ImageSections(e) => f.write_fmt(format_args!("failed to read image sections: {}", e)),
             PdfWrite(e) => f.write_fmt(format_args!("failed to write PDF: {}", e)),
+            UnknownFormat => f.write_fmt(format_args!(
+                "could not detect the image format from its magic bytes"
+            )),
+            ImageCodec(e) => f.write_fmt(format_args!("failed to decode or encode image: {}", e)),
+            ImageCompress(e) => {
+                f.write_fmt(format_args!("failed to compress image data: {}", e))
+            }
+            Jpeg2000Header => {
+                f.write_fmt(format_args!("failed to read JPEG 2000 image header"))
+            }
+            TiffDecode(e) => f.write_fmt(format_args!("failed to decode TIFF: {}", e)),
+            UnsupportedTiffFrame => f.write_fmt(format_args!(
+                "TIFF frame uses an unsupported sample format or color type"
+            )),
         }
     }
 }
@@ -57,3 +77,15 @@ impl From<img_parts::Error> for Cause {
         Cause::ImageSections(e)
     }
 }
+
+impl From<image::ImageError> for Cause {
+    fn from(e: image::ImageError) -> Cause {
+        Cause::ImageCodec(e)
+    }
+}
+
+impl From<tiff::TiffError> for Cause {
+    fn from(e: tiff::TiffError) -> Cause {
+        Cause::TiffDecode(e)
+    }
+}
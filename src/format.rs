@@ -0,0 +1,82 @@
+/// Image container formats that [`crate::JpegToPdf::add_image_auto`] can detect from a
+/// buffer's leading magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImageFormat {
+    Jpeg,
+    Png,
+    Bmp,
+    Tiff,
+    Jpeg2000,
+}
+
+impl ImageFormat {
+    /// Sniffs the format of `data` from its magic bytes, returning `None` if none match.
+    pub(crate) fn detect(data: &[u8]) -> Option<ImageFormat> {
+        const JP2_SIGNATURE: [u8; 12] = [
+            0x00, 0x00, 0x00, 0x0C, 0x6A, 0x50, 0x20, 0x20, 0x0D, 0x0A, 0x87, 0x0A,
+        ];
+
+        if data.starts_with(&[0xFF, 0xD8]) {
+            Some(ImageFormat::Jpeg)
+        } else if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+            Some(ImageFormat::Png)
+        } else if data.starts_with(b"BM") {
+            Some(ImageFormat::Bmp)
+        } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+            Some(ImageFormat::Tiff)
+        } else if data.starts_with(&JP2_SIGNATURE) {
+            Some(ImageFormat::Jpeg2000)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_jpeg() {
+        assert_eq!(ImageFormat::detect(&[0xFF, 0xD8, 0xFF, 0xE0]), Some(ImageFormat::Jpeg));
+    }
+
+    #[test]
+    fn detects_png() {
+        let data = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00];
+        assert_eq!(ImageFormat::detect(&data), Some(ImageFormat::Png));
+    }
+
+    #[test]
+    fn detects_bmp() {
+        assert_eq!(ImageFormat::detect(b"BM1234"), Some(ImageFormat::Bmp));
+    }
+
+    #[test]
+    fn detects_tiff_little_endian() {
+        assert_eq!(ImageFormat::detect(b"II*\0rest"), Some(ImageFormat::Tiff));
+    }
+
+    #[test]
+    fn detects_tiff_big_endian() {
+        assert_eq!(ImageFormat::detect(b"MM\0*rest"), Some(ImageFormat::Tiff));
+    }
+
+    #[test]
+    fn detects_jpeg2000() {
+        let data = [
+            0x00, 0x00, 0x00, 0x0C, 0x6A, 0x50, 0x20, 0x20, 0x0D, 0x0A, 0x87, 0x0A, 0x00, 0x00,
+        ];
+        assert_eq!(ImageFormat::detect(&data), Some(ImageFormat::Jpeg2000));
+    }
+
+    #[test]
+    fn returns_none_for_unknown_magic_bytes() {
+        assert_eq!(ImageFormat::detect(b"not an image"), None);
+    }
+
+    #[test]
+    fn returns_none_for_empty_buffer() {
+        assert_eq!(ImageFormat::detect(&[]), None);
+    }
+}
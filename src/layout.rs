@@ -0,0 +1,230 @@
+/// A named paper size, or a custom size in millimeters, for [`crate::JpegToPdf::page_layout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaBox {
+    A4,
+    Letter,
+    /// A custom size in millimeters: `Mm(width, height)`.
+    Mm(f64, f64),
+}
+
+impl MediaBox {
+    fn portrait_mm(self) -> (f64, f64) {
+        match self {
+            MediaBox::A4 => (210.0, 297.0),
+            MediaBox::Letter => (215.9, 279.4),
+            MediaBox::Mm(w, h) => (w.min(h), w.max(h)),
+        }
+    }
+}
+
+/// How a fixed page's orientation is chosen, for [`crate::JpegToPdf::page_layout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageOrientation {
+    /// Landscape if the image is wider than it is tall, portrait otherwise.
+    Auto,
+    Portrait,
+    Landscape,
+}
+
+/// How an image is scaled to fit a fixed page, for [`crate::JpegToPdf::page_layout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FitPolicy {
+    /// Scale down (or up) so the whole image fits within the page, leaving margins on the
+    /// shorter axis.
+    Contain,
+    /// Scale so the image fills the page entirely, extending past it on the longer axis.
+    Fill,
+}
+
+/// Where a [`FitPolicy`]-scaled image is anchored within any leftover space, for
+/// [`crate::JpegToPdf::page_layout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    Center,
+    TopLeft,
+}
+
+/// Fits every page to a fixed paper size instead of sizing it from the image's own pixel
+/// dimensions and DPI. Set via [`crate::JpegToPdf::page_layout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageLayout {
+    pub media_box: MediaBox,
+    pub orientation: PageOrientation,
+    pub fit: FitPolicy,
+    pub anchor: Anchor,
+}
+
+/// The computed placement of an image on a [`PageLayout`]'s fixed page: the page size in
+/// millimeters, the DPI that scales the image to the chosen fit, and the millimeter-space
+/// translation that applies the chosen anchor.
+pub(crate) struct Placement {
+    pub page_width_mm: f64,
+    pub page_height_mm: f64,
+    pub dpi: f64,
+    pub translate_x_mm: f64,
+    pub translate_y_mm: f64,
+}
+
+impl PageLayout {
+    pub(crate) fn place(&self, image_width_px: usize, image_height_px: usize) -> Placement {
+        let (portrait_w, portrait_h) = self.media_box.portrait_mm();
+        let landscape = match self.orientation {
+            PageOrientation::Auto => image_width_px > image_height_px,
+            PageOrientation::Portrait => false,
+            PageOrientation::Landscape => true,
+        };
+        let (page_width_mm, page_height_mm) = if landscape {
+            (portrait_h, portrait_w)
+        } else {
+            (portrait_w, portrait_h)
+        };
+
+        let scale_w = page_width_mm / image_width_px as f64;
+        let scale_h = page_height_mm / image_height_px as f64;
+        let scale_mm_per_px = match self.fit {
+            FitPolicy::Contain => scale_w.min(scale_h),
+            FitPolicy::Fill => scale_w.max(scale_h),
+        };
+
+        let display_width_mm = image_width_px as f64 * scale_mm_per_px;
+        let display_height_mm = image_height_px as f64 * scale_mm_per_px;
+
+        let (translate_x_mm, translate_y_mm) = match self.anchor {
+            Anchor::Center => (
+                (page_width_mm - display_width_mm) / 2.0,
+                (page_height_mm - display_height_mm) / 2.0,
+            ),
+            Anchor::TopLeft => (0.0, page_height_mm - display_height_mm),
+        };
+
+        Placement {
+            page_width_mm,
+            page_height_mm,
+            dpi: 25.4 / scale_mm_per_px,
+            translate_x_mm,
+            translate_y_mm,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(media_box: MediaBox, orientation: PageOrientation, fit: FitPolicy, anchor: Anchor) -> PageLayout {
+        PageLayout {
+            media_box,
+            orientation,
+            fit,
+            anchor,
+        }
+    }
+
+    #[test]
+    fn contain_centers_with_margins_on_the_shorter_axis() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 100.0),
+            PageOrientation::Portrait,
+            FitPolicy::Contain,
+            Anchor::Center,
+        );
+        let placement = layout.place(200, 100);
+
+        assert_eq!(placement.page_width_mm, 100.0);
+        assert_eq!(placement.page_height_mm, 100.0);
+        assert_eq!(placement.dpi, 50.8);
+        assert_eq!(placement.translate_x_mm, 0.0);
+        assert_eq!(placement.translate_y_mm, 25.0);
+    }
+
+    #[test]
+    fn fill_overflows_past_the_page_on_the_longer_axis() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 100.0),
+            PageOrientation::Portrait,
+            FitPolicy::Fill,
+            Anchor::Center,
+        );
+        let placement = layout.place(200, 100);
+
+        assert_eq!(placement.dpi, 25.4);
+        assert_eq!(placement.translate_x_mm, -50.0);
+        assert_eq!(placement.translate_y_mm, 0.0);
+    }
+
+    #[test]
+    fn top_left_anchor_leaves_the_margin_below_and_to_the_right() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 100.0),
+            PageOrientation::Portrait,
+            FitPolicy::Contain,
+            Anchor::TopLeft,
+        );
+        let placement = layout.place(200, 100);
+
+        assert_eq!(placement.translate_x_mm, 0.0);
+        assert_eq!(placement.translate_y_mm, 50.0);
+    }
+
+    #[test]
+    fn auto_orientation_goes_landscape_for_a_wider_image() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 200.0),
+            PageOrientation::Auto,
+            FitPolicy::Contain,
+            Anchor::Center,
+        );
+        let placement = layout.place(200, 100);
+
+        assert_eq!(placement.page_width_mm, 200.0);
+        assert_eq!(placement.page_height_mm, 100.0);
+    }
+
+    #[test]
+    fn auto_orientation_goes_portrait_for_a_taller_image() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 200.0),
+            PageOrientation::Auto,
+            FitPolicy::Contain,
+            Anchor::Center,
+        );
+        let placement = layout.place(100, 200);
+
+        assert_eq!(placement.page_width_mm, 100.0);
+        assert_eq!(placement.page_height_mm, 200.0);
+    }
+
+    #[test]
+    fn explicit_orientation_overrides_the_images_own_shape() {
+        let layout = layout(
+            MediaBox::Mm(100.0, 200.0),
+            PageOrientation::Landscape,
+            FitPolicy::Contain,
+            Anchor::Center,
+        );
+        let placement = layout.place(100, 200);
+
+        assert_eq!(placement.page_width_mm, 200.0);
+        assert_eq!(placement.page_height_mm, 100.0);
+    }
+
+    #[test]
+    fn mm_media_box_normalizes_to_portrait_regardless_of_argument_order() {
+        let layout = layout(
+            MediaBox::Mm(200.0, 100.0),
+            PageOrientation::Portrait,
+            FitPolicy::Contain,
+            Anchor::Center,
+        );
+        let placement = layout.place(1, 1);
+
+        assert_eq!(placement.page_width_mm, 100.0);
+        assert_eq!(placement.page_height_mm, 200.0);
+    }
+
+    #[test]
+    fn named_paper_sizes_use_their_standard_portrait_dimensions() {
+        assert_eq!(MediaBox::A4.portrait_mm(), (210.0, 297.0));
+        assert_eq!(MediaBox::Letter.portrait_mm(), (215.9, 279.4));
+    }
+}
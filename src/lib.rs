@@ -24,24 +24,57 @@
 use errors::Error;
 pub use errors::*;
 use exif::{In, Reader as ExifReader, Tag};
+use flate2::{write::ZlibEncoder, Compression};
+use format::ImageFormat;
+use image::codecs::jpeg::JpegEncoder;
+use image::{imageops, DynamicImage, GenericImageView};
 use img_parts::{jpeg::Jpeg, ImageEXIF};
 use jpeg_decoder::{Decoder as JpegDecoder, PixelFormat};
+pub use layout::*;
 use ori::Orientation;
 use printpdf::*;
 use std::io::{prelude::*, BufWriter, Cursor};
+use time::{Date, PrimitiveDateTime, Time, UtcOffset};
+use tiff::decoder::{Decoder as TiffDecoder, DecodingResult};
+use tiff::tags::{CompressionMethod, Tag as TiffTag};
+use tiff::ColorType as TiffColorType;
 
 mod errors;
+mod format;
+mod layout;
 mod ori;
 
-mod tests;
+/// An image queued up for embedding, tagged with how its format should be determined.
+enum ImageSource {
+    /// Assumed to be a JPEG without inspecting its contents; the existing fast path.
+    Jpeg(Vec<u8>),
+    /// Format is sniffed from the buffer's magic bytes in [`add_page`].
+    Auto(Vec<u8>),
+}
+
+impl ImageSource {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            ImageSource::Jpeg(bytes) | ImageSource::Auto(bytes) => bytes,
+        }
+    }
+}
+
 /// Creates a PDF from JPEG images.
 pub struct JpegToPdf {
-    images: Vec<Vec<u8>>,
+    images: Vec<ImageSource>,
     dpi: f64,
+    use_image_dpi: bool,
     strip_exif: bool,
     document_title: String,
     creation_date: OffsetDateTime,
+    creation_date_set: bool,
     mod_date: OffsetDateTime,
+    mod_date_set: bool,
+    import_metadata: bool,
+    max_dimension: Option<u32>,
+    recompress_quality: Option<u8>,
+    page_layout: Option<PageLayout>,
 }
 
 impl JpegToPdf {
@@ -49,31 +82,75 @@ impl JpegToPdf {
         JpegToPdf {
             images: Vec::new(),
             dpi: 300.0,
+            use_image_dpi: false,
             strip_exif: false,
             document_title: String::new(),
             creation_date: OffsetDateTime::now_utc(),
+            creation_date_set: false,
             mod_date: OffsetDateTime::now_utc(),
+            mod_date_set: false,
+            import_metadata: false,
+            max_dimension: None,
+            recompress_quality: None,
+            page_layout: None,
         }
     }
 
-    /// Add an image to the PDF output.
+    /// Add a JPEG image to the PDF output.
+    ///
+    /// The buffer is assumed to already be a JPEG and is embedded as-is, without re-encoding,
+    /// unless [`max_dimension`](JpegToPdf::max_dimension) or
+    /// [`recompress_quality`](JpegToPdf::recompress_quality) calls for re-encoding it. Use [`add_image_auto`](JpegToPdf::add_image_auto) to accept other formats.
     pub fn add_image(mut self, image: Vec<u8>) -> JpegToPdf {
-        self.images.push(image);
+        self.images.push(ImageSource::Jpeg(image));
         self
     }
 
-    /// Add one or more images to the PDF output.
+    /// Add one or more JPEG images to the PDF output.
+    ///
+    /// See [`add_image`](JpegToPdf::add_image) for the assumptions made about each buffer.
     pub fn add_images(mut self, images: impl IntoIterator<Item = Vec<u8>>) -> JpegToPdf {
-        self.images.extend(images);
+        self.images.extend(images.into_iter().map(ImageSource::Jpeg));
+        self
+    }
+
+    /// Add an image of any supported format to the PDF output.
+    ///
+    /// The format is detected from the buffer's magic bytes: JPEG is embedded as-is, PNG/BMP/TIFF
+    /// are decoded and embedded with Flate (JPEG-compressed TIFF frames are embedded as DCT
+    /// without decoding), and JPEG 2000 has its bare codestream embedded without
+    /// re-encoding. 8-bit greyscale and RGB PNG/BMP images are lossless; other PNG/BMP pixel
+    /// formats are converted to 8-bit RGB, dropping alpha and extra bit depth. Use [`add_image`]
+    /// instead if every image is already known to be a JPEG, to skip the detection step.
+    pub fn add_image_auto(mut self, image: Vec<u8>) -> JpegToPdf {
+        self.images.push(ImageSource::Auto(image));
         self
     }
 
     /// Set the DPI scaling of the PDF output.
+    ///
+    /// This is a fallback used for images that carry no resolution metadata, or when
+    /// [`use_image_dpi`](JpegToPdf::use_image_dpi) is left disabled. TIFF frames that carry a
+    /// resolution tag always use it instead.
     pub fn set_dpi(mut self, dpi: f64) -> JpegToPdf {
         self.dpi = dpi;
         self
     }
 
+    /// Derive each image's page size from its own EXIF/JFIF resolution instead of the single
+    /// DPI set via [`set_dpi`](JpegToPdf::set_dpi).
+    ///
+    /// Images with no resolution metadata still fall back to `set_dpi`. Only applies to JPEG
+    /// images that are embedded without re-encoding; JPEGs re-encoded via
+    /// [`max_dimension`](JpegToPdf::max_dimension) or
+    /// [`recompress_quality`](JpegToPdf::recompress_quality) use `set_dpi`. TIFF frames always
+    /// use their own resolution tag when present, regardless of this setting, and other formats
+    /// always use `set_dpi`.
+    pub fn use_image_dpi(mut self, use_image_dpi: bool) -> JpegToPdf {
+        self.use_image_dpi = use_image_dpi;
+        self
+    }
+
     /// Strip EXIF metadata from the provided images.
     ///
     /// Some PDF renderers have issues rendering JPEG images that still have EXIF metadata.
@@ -91,28 +168,122 @@ impl JpegToPdf {
     /// Sets the creation date of the PDF output.
     pub fn set_creation_date(mut self, creation_date: OffsetDateTime) -> JpegToPdf {
         self.creation_date = creation_date;
+        self.creation_date_set = true;
         self
     }
 
     /// Sets the modification date of the PDF output.
     pub fn set_mod_date(mut self, mod_date: OffsetDateTime) -> JpegToPdf {
         self.mod_date = mod_date;
+        self.mod_date_set = true;
+        self
+    }
+
+    /// Populate the PDF's title, author, producer, keywords, and dates from the first image's
+    /// EXIF data, wherever the caller hasn't already set them explicitly.
+    ///
+    /// Maps `ImageDescription`/`DocumentName` to the title, `Artist` to the author, `Software` to
+    /// the producer, `Copyright` to the keywords, and `DateTimeOriginal`/`DateTime` to the
+    /// creation/modification dates. Any tag that is absent, or whose PDF counterpart was already
+    /// set explicitly, is left untouched.
+    ///
+    /// `DateTimeOriginal`/`DateTime` carry no timezone and are interpreted as local time to this
+    /// process, falling back to UTC when the process can't determine its local offset soundly
+    /// (notably, in a multi-threaded process).
+    pub fn import_metadata(mut self, import_metadata: bool) -> JpegToPdf {
+        self.import_metadata = import_metadata;
+        self
+    }
+
+    /// Cap the pixel dimensions of embedded JPEGs, downscaling oversized images with a Lanczos3
+    /// filter. Images already within bounds are left untouched unless
+    /// [`recompress_quality`](JpegToPdf::recompress_quality) is also set.
+    ///
+    /// Only applies to JPEG images; enabling it bakes in EXIF orientation destructively and
+    /// re-encodes the image, so the output is no longer byte-identical to the source.
+    pub fn max_dimension(mut self, max_dimension: Option<u32>) -> JpegToPdf {
+        self.max_dimension = max_dimension;
+        self
+    }
+
+    /// Re-encode embedded JPEGs to baseline JPEG at the given quality (0-100), to cap output
+    /// file size. See [`max_dimension`](JpegToPdf::max_dimension) for the accompanying caveats.
+    pub fn recompress_quality(mut self, recompress_quality: Option<u8>) -> JpegToPdf {
+        self.recompress_quality = recompress_quality;
+        self
+    }
+
+    /// Fit every page to a fixed paper size, rather than sizing it from each image's own pixel
+    /// dimensions and DPI.
+    pub fn page_layout(mut self, page_layout: Option<PageLayout>) -> JpegToPdf {
+        self.page_layout = page_layout;
         self
     }
 
     /// Writes the PDF output to `out`.
     pub fn create_pdf(self, out: &mut BufWriter<impl Write>) -> Result<(), Error> {
-        let (dpi, strip_exif) = (self.dpi, self.strip_exif);
+        let (dpi, strip_exif, use_image_dpi) = (self.dpi, self.strip_exif, self.use_image_dpi);
+        let (max_dimension, recompress_quality) = (self.max_dimension, self.recompress_quality);
+        let page_layout = self.page_layout;
 
-        let doc = PdfDocument::empty(self.document_title)
-            .with_creation_date(self.creation_date)
-            .with_mod_date(self.mod_date);
+        let metadata = self
+            .import_metadata
+            .then(|| first_image_metadata(&self.images))
+            .flatten();
+
+        let mut document_title = self.document_title;
+        let mut creation_date = self.creation_date;
+        let mut mod_date = self.mod_date;
+
+        if let Some(metadata) = &metadata {
+            if document_title.is_empty() {
+                if let Some(title) = &metadata.title {
+                    document_title = title.clone();
+                }
+            }
+            if !self.creation_date_set {
+                if let Some(date) = metadata.date_time_original {
+                    creation_date = date;
+                }
+            }
+            if !self.mod_date_set {
+                if let Some(date) = metadata.date_time {
+                    mod_date = date;
+                }
+            }
+        }
+
+        let mut doc = PdfDocument::empty(document_title)
+            .with_creation_date(creation_date)
+            .with_mod_date(mod_date);
+
+        if let Some(metadata) = metadata {
+            if let Some(author) = metadata.author {
+                doc = doc.with_author(author);
+            }
+            if let Some(producer) = metadata.producer {
+                doc = doc.with_producer(producer);
+            }
+            if let Some(keywords) = metadata.keywords {
+                doc = doc.with_keywords(vec![keywords]);
+            }
+        }
 
         self.images
             .into_iter()
             .enumerate()
             .try_for_each(|(index, image)| {
-                add_page(image, &doc, dpi, strip_exif).map_err(|cause| Error { index, cause })
+                add_page(
+                    image,
+                    &doc,
+                    dpi,
+                    strip_exif,
+                    use_image_dpi,
+                    max_dimension,
+                    recompress_quality,
+                    page_layout,
+                )
+                .map_err(|cause| Error { index, cause })
             })
             .and_then(|()| {
                 doc.save(out).map_err(|e| Error {
@@ -124,22 +295,126 @@ impl JpegToPdf {
 }
 
 fn add_page(
+    image: ImageSource,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    strip_exif: bool,
+    use_image_dpi: bool,
+    max_dimension: Option<u32>,
+    recompress_quality: Option<u8>,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    match image {
+        ImageSource::Jpeg(image) => add_jpeg_page(
+            image,
+            doc,
+            dpi,
+            strip_exif,
+            use_image_dpi,
+            max_dimension,
+            recompress_quality,
+            page_layout,
+        ),
+        ImageSource::Auto(image) => match ImageFormat::detect(&image) {
+            Some(ImageFormat::Jpeg) => add_jpeg_page(
+                image,
+                doc,
+                dpi,
+                strip_exif,
+                use_image_dpi,
+                max_dimension,
+                recompress_quality,
+                page_layout,
+            ),
+            Some(ImageFormat::Png) | Some(ImageFormat::Bmp) => {
+                add_raster_page(image, doc, dpi, page_layout)
+            }
+            Some(ImageFormat::Tiff) => add_tiff_pages(image, doc, dpi, page_layout),
+            Some(ImageFormat::Jpeg2000) => add_jpeg2000_page(image, doc, dpi, page_layout),
+            None => Err(Cause::UnknownFormat),
+        },
+    }
+}
+
+/// Computes the page size, per-image DPI, and translation to use for an embedded image, given
+/// an optional fixed [`PageLayout`]. Without a layout, this is just the existing DPI-driven page
+/// sizing with no translation; with one, the page is fixed and the image is scaled, and possibly
+/// translated, to fit it.
+fn page_geometry(
+    layout: Option<PageLayout>,
+    width_px: usize,
+    height_px: usize,
+    dpi: (f64, f64),
+) -> (Mm, Mm, (f64, f64), Option<Mm>, Option<Mm>) {
+    match layout {
+        Some(layout) => {
+            let placement = layout.place(width_px, height_px);
+            (
+                Mm(placement.page_width_mm),
+                Mm(placement.page_height_mm),
+                (placement.dpi, placement.dpi),
+                Some(Mm(placement.translate_x_mm)),
+                Some(Mm(placement.translate_y_mm)),
+            )
+        }
+        None => (
+            Px(width_px).into_pt(dpi.0).into(),
+            Px(height_px).into_pt(dpi.1).into(),
+            dpi,
+            None,
+            None,
+        ),
+    }
+}
+
+/// Sums two optional millimeter offsets, collapsing to `None` only when both are `None` so
+/// unlayered pages keep passing `None` through to `add_to_layer` exactly as before.
+fn sum_mm(a: Option<Mm>, b: Option<Mm>) -> Option<Mm> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(Mm(a.map_or(0.0, |m| m.0) + b.map_or(0.0, |m| m.0))),
+    }
+}
+
+fn add_jpeg_page(
     image: Vec<u8>,
     doc: &PdfDocumentReference,
     dpi: f64,
     strip_exif: bool,
+    use_image_dpi: bool,
+    max_dimension: Option<u32>,
+    recompress_quality: Option<u8>,
+    page_layout: Option<PageLayout>,
 ) -> Result<(), Cause> {
     let mut decoder = JpegDecoder::new(Cursor::new(&image));
     decoder.read_info()?;
 
+    let oversized = max_dimension.zip(decoder.info()).is_some_and(|(max, info)| {
+        info.width as u32 > max || info.height as u32 > max
+    });
+
+    if recompress_quality.is_some() || oversized {
+        return add_recompressed_jpeg_page(
+            image,
+            doc,
+            dpi,
+            max_dimension,
+            recompress_quality,
+            page_layout,
+        );
+    }
+
     match decoder.info() {
         None => Err(Cause::UnexpectedImageInfo), // decoder.read_info would return Err, so we should never see this
         Some(info) => {
             let mut image = Jpeg::from_bytes(image.into())?;
 
-            let ori = image
+            let exif = image
                 .exif()
-                .and_then(|exif_data| ExifReader::new().read_raw(exif_data.to_vec()).ok())
+                .and_then(|exif_data| ExifReader::new().read_raw(exif_data.to_vec()).ok());
+
+            let ori = exif
+                .as_ref()
                 .and_then(|exif| {
                     exif.get_field(Tag::Orientation, In::PRIMARY)
                         .and_then(|field| field.value.get_uint(0))
@@ -152,6 +427,15 @@ fn add_page(
                 height: info.height as usize,
             };
 
+            let dpi = if use_image_dpi {
+                exif.as_ref()
+                    .and_then(exif_dpi)
+                    .or_else(|| jfif_density(&image))
+                    .unwrap_or((dpi, dpi))
+            } else {
+                (dpi, dpi)
+            };
+
             if strip_exif {
                 image.set_exif(None);
             }
@@ -159,11 +443,10 @@ fn add_page(
             let mut image_data = Vec::new();
             image.encoder().write_to(&mut image_data).unwrap();
 
-            let (page, layer) = doc.add_page(
-                Px(ori.display_width()).into_pt(dpi).into(),
-                Px(ori.display_height()).into_pt(dpi).into(),
-                "",
-            );
+            let (page_width, page_height, dpi, layout_translate_x, layout_translate_y) =
+                page_geometry(page_layout, ori.display_width(), ori.display_height(), dpi);
+
+            let (page, layer) = doc.add_page(page_width, page_height, "");
 
             let image = Image::from(ImageXObject {
                 width: Px(info.width as usize),
@@ -180,14 +463,27 @@ fn add_page(
                 clipping_bbox: None,
             });
 
+            // add_to_layer only takes a single `dpi` to size the image from its pixel count, so
+            // passing just dpi.0 would scale the Y axis by the X resolution too, stretching the
+            // image whenever EXIF/JFIF report different X/Y resolutions. Correct for that with an
+            // extra Y scale factor; when the two match (the common case, and always true once a
+            // fixed page_layout has picked a single dpi) this is just 1.0.
+            let aspect_correction = if dpi.1 != 0.0 { dpi.0 / dpi.1 } else { 1.0 };
+
             image.add_to_layer(
                 doc.get_page(page).get_layer(layer),
-                ori.translate_x().map(|px| Px(px).into_pt(dpi).into()),
-                ori.translate_y().map(|px| Px(px).into_pt(dpi).into()),
+                sum_mm(
+                    ori.translate_x().map(|px| Px(px).into_pt(dpi.0).into()),
+                    layout_translate_x,
+                ),
+                sum_mm(
+                    ori.translate_y().map(|px| Px(px).into_pt(dpi.1).into()),
+                    layout_translate_y,
+                ),
                 ori.rotate_cw(),
                 ori.scale_x(),
-                None,
-                Some(dpi),
+                Some(aspect_correction),
+                Some(dpi.0),
             );
 
             Ok(())
@@ -195,6 +491,566 @@ fn add_page(
     }
 }
 
+/// Decodes a JPEG to pixels, bakes in its EXIF orientation destructively, downscales to
+/// `max_dimension` with a Lanczos3 filter, and re-encodes at `recompress_quality` before
+/// embedding. Since orientation is baked into the raster, the page is sized directly from the
+/// re-encoded image with no further [`Orientation`] transform needed.
+fn add_recompressed_jpeg_page(
+    image: Vec<u8>,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    max_dimension: Option<u32>,
+    recompress_quality: Option<u8>,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    let ori = Jpeg::from_bytes(image.clone().into())
+        .ok()
+        .and_then(|jpeg| {
+            jpeg.exif()
+                .and_then(|exif_data| ExifReader::new().read_raw(exif_data.to_vec()).ok())
+        })
+        .and_then(|exif| {
+            exif.get_field(Tag::Orientation, In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .unwrap_or(1);
+
+    let oriented = apply_exif_orientation(image::load_from_memory(&image)?, ori);
+    let oriented = match oriented {
+        DynamicImage::ImageLuma8(buf) => DynamicImage::ImageLuma8(buf),
+        other => DynamicImage::ImageRgb8(other.to_rgb8()),
+    };
+
+    let (width, height) = oriented.dimensions();
+    let resized = match max_dimension {
+        Some(max) if width > max || height > max => {
+            oriented.resize(max, max, imageops::FilterType::Lanczos3)
+        }
+        _ => oriented,
+    };
+
+    let color_space = match &resized {
+        DynamicImage::ImageLuma8(_) => ColorSpace::Greyscale,
+        _ => ColorSpace::Rgb,
+    };
+
+    let mut image_data = Vec::new();
+    JpegEncoder::new_with_quality(&mut image_data, recompress_quality.unwrap_or(85))
+        .encode_image(&resized)?;
+
+    let (width, height) = resized.dimensions();
+    let (width, height) = (width as usize, height as usize);
+
+    let (page_width, page_height, dpi, translate_x, translate_y) =
+        page_geometry(page_layout, width, height, (dpi, dpi));
+
+    let (page, layer) = doc.add_page(page_width, page_height, "");
+
+    let pdf_image = Image::from(ImageXObject {
+        width: Px(width),
+        height: Px(height),
+        color_space,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: false,
+        image_data,
+        image_filter: Some(ImageFilter::DCT),
+        clipping_bbox: None,
+    });
+
+    pdf_image.add_to_layer(
+        doc.get_page(page).get_layer(layer),
+        translate_x,
+        translate_y,
+        None,
+        None,
+        None,
+        Some(dpi.0),
+    );
+
+    Ok(())
+}
+
+/// Bakes an EXIF orientation value into an image's pixels, so it can be embedded without a PDF
+/// rotation transform.
+fn apply_exif_orientation(image: DynamicImage, value: u32) -> DynamicImage {
+    match value {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// Derives the physical DPI of a JPEG from its EXIF `XResolution`/`YResolution` tags, converting
+/// centimeters to inches per `ResolutionUnit`. Returns `None` if either tag is absent.
+fn exif_dpi(exif: &exif::Exif) -> Option<(f64, f64)> {
+    let resolution = |tag| match exif.get_field(tag, In::PRIMARY)?.value {
+        exif::Value::Rational(ref v) => v.first().map(exif::Rational::to_f64),
+        _ => None,
+    };
+
+    let x = resolution(Tag::XResolution)?;
+    let y = resolution(Tag::YResolution)?;
+
+    let unit = exif
+        .get_field(Tag::ResolutionUnit, In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .unwrap_or(2);
+
+    Some(if unit == 3 {
+        (x * 2.54, y * 2.54)
+    } else {
+        (x, y)
+    })
+}
+
+/// Derives the physical DPI of a JPEG from its JFIF APP0 segment's density fields, for images
+/// that carry no EXIF resolution. Returns `None` if there is no JFIF segment or its density unit
+/// is 0 (aspect ratio only, no physical resolution).
+fn jfif_density(image: &Jpeg) -> Option<(f64, f64)> {
+    const APP0: u8 = 0xE0;
+
+    let app0 = image
+        .segments()
+        .iter()
+        .find(|segment| segment.marker() == APP0 && segment.contents().starts_with(b"JFIF\0"))?
+        .contents();
+
+    let unit = *app0.get(7)?;
+    if unit == 0 {
+        return None;
+    }
+
+    let x_density = u16::from_be_bytes(app0.get(8..10)?.try_into().ok()?) as f64;
+    let y_density = u16::from_be_bytes(app0.get(10..12)?.try_into().ok()?) as f64;
+
+    Some(if unit == 2 {
+        (x_density * 2.54, y_density * 2.54)
+    } else {
+        (x_density, y_density)
+    })
+}
+
+/// PDF document metadata gathered from a JPEG's EXIF tags for [`JpegToPdf::import_metadata`].
+struct ExifMetadata {
+    title: Option<String>,
+    author: Option<String>,
+    producer: Option<String>,
+    keywords: Option<String>,
+    date_time_original: Option<OffsetDateTime>,
+    date_time: Option<OffsetDateTime>,
+}
+
+/// Reads [`ExifMetadata`] out of the first image, if it's a JPEG carrying EXIF data.
+fn first_image_metadata(images: &[ImageSource]) -> Option<ExifMetadata> {
+    let jpeg = Jpeg::from_bytes(images.first()?.bytes().to_vec().into()).ok()?;
+    let exif = ExifReader::new()
+        .read_raw(jpeg.exif()?.to_vec())
+        .ok()?;
+
+    Some(ExifMetadata {
+        title: ascii_tag(&exif, Tag::ImageDescription).or_else(|| ascii_tag(&exif, Tag::DocumentName)),
+        author: ascii_tag(&exif, Tag::Artist),
+        producer: ascii_tag(&exif, Tag::Software),
+        keywords: ascii_tag(&exif, Tag::Copyright),
+        date_time_original: exif_date(&exif, Tag::DateTimeOriginal),
+        date_time: exif_date(&exif, Tag::DateTime),
+    })
+}
+
+/// Reads an ASCII-valued EXIF tag as a trimmed `String`.
+fn ascii_tag(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        exif::Value::Ascii(values) => values
+            .first()
+            .map(|v| String::from_utf8_lossy(v).trim_end_matches('\0').to_string()),
+        _ => None,
+    }
+}
+
+/// Parses an EXIF `DateTime`-format tag (`"YYYY:MM:DD HH:MM:SS"`) as a local [`OffsetDateTime`].
+///
+/// EXIF carries no timezone, so the naive timestamp is assumed to already be in the local offset
+/// of wherever this process is running, per the tag's own semantics. That offset can't always be
+/// determined soundly (the `time` crate refuses to read it in a multi-threaded process), in which
+/// case this falls back to UTC rather than failing outright.
+fn exif_date(exif: &exif::Exif, tag: Tag) -> Option<OffsetDateTime> {
+    parse_exif_datetime(&ascii_tag(exif, tag)?)
+}
+
+fn parse_exif_datetime(raw: &str) -> Option<OffsetDateTime> {
+    let year = raw.get(0..4)?.parse().ok()?;
+    let month = raw.get(5..7)?.parse::<u8>().ok()?.try_into().ok()?;
+    let day = raw.get(8..10)?.parse().ok()?;
+    let hour = raw.get(11..13)?.parse().ok()?;
+    let minute = raw.get(14..16)?.parse().ok()?;
+    let second = raw.get(17..19)?.parse().ok()?;
+
+    let date = Date::from_calendar_date(year, month, day).ok()?;
+    let time = Time::from_hms(hour, minute, second).ok()?;
+
+    let offset = UtcOffset::local_offset_at(OffsetDateTime::now_utc()).unwrap_or(UtcOffset::UTC);
+    Some(PrimitiveDateTime::new(date, time).assume_offset(offset))
+}
+
+/// Embeds a PNG or BMP image by decoding it to raw samples and re-compressing them with Flate,
+/// since the PDF spec has no native container for either format. 8-bit greyscale and RGB images
+/// are embedded losslessly; any other pixel format (e.g. with alpha, or 16 bits per channel) is
+/// converted to 8-bit RGB first, dropping alpha and extra bit depth.
+fn add_raster_page(
+    image: Vec<u8>,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    let decoded = image::load_from_memory(&image)?;
+    let (width, height) = decoded.dimensions();
+    let (width, height) = (width as usize, height as usize);
+
+    let (color_space, samples) = match decoded {
+        DynamicImage::ImageLuma8(buf) => (ColorSpace::Greyscale, buf.into_raw()),
+        DynamicImage::ImageRgb8(buf) => (ColorSpace::Rgb, buf.into_raw()),
+        other => (ColorSpace::Rgb, other.to_rgb8().into_raw()),
+    };
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&samples)
+        .and_then(|()| encoder.finish())
+        .map_err(Cause::ImageCompress)
+        .map(|image_data| {
+            let (page_width, page_height, dpi, translate_x, translate_y) =
+                page_geometry(page_layout, width, height, (dpi, dpi));
+
+            let (page, layer) = doc.add_page(page_width, page_height, "");
+
+            let image = Image::from(ImageXObject {
+                width: Px(width),
+                height: Px(height),
+                color_space,
+                bits_per_component: ColorBits::Bit8,
+                interpolate: false,
+                image_data,
+                image_filter: Some(ImageFilter::Flate),
+                clipping_bbox: None,
+            });
+
+            image.add_to_layer(
+                doc.get_page(page).get_layer(layer),
+                translate_x,
+                translate_y,
+                None,
+                None,
+                None,
+                Some(dpi.0),
+            );
+        })
+}
+
+/// Explodes a (possibly multi-page) TIFF into one PDF page per image file directory, preserving
+/// each frame's own resolution tags so page sizing stays correct across frames that differ in
+/// dimensions.
+fn add_tiff_pages(
+    image: Vec<u8>,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    let raw = image;
+    let mut decoder = TiffDecoder::new(Cursor::new(&raw))?;
+
+    loop {
+        add_tiff_frame(&raw, &mut decoder, doc, dpi, page_layout)?;
+
+        if !decoder.more_images() {
+            break;
+        }
+        decoder.next_image()?;
+    }
+
+    Ok(())
+}
+
+fn add_tiff_frame(
+    raw: &[u8],
+    decoder: &mut TiffDecoder<Cursor<&Vec<u8>>>,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    let (width, height) = decoder.dimensions()?;
+    let (width, height) = (width as usize, height as usize);
+    let frame_dpi = tiff_resolution(decoder).unwrap_or(dpi);
+
+    let compression = decoder
+        .get_tag_u32(TiffTag::Compression)
+        .unwrap_or(CompressionMethod::None as u32);
+
+    let image = if compression == CompressionMethod::ModernJPEG as u32 {
+        // Baseline JPEG-in-TIFF: re-embed the compressed strip bytes directly as DCT, rather
+        // than decoding and re-encoding them.
+        let color_space = match decoder.colortype()? {
+            TiffColorType::Gray(8) => ColorSpace::Greyscale,
+            TiffColorType::RGB(8) => ColorSpace::Rgb,
+            TiffColorType::CMYK(8) => ColorSpace::Cmyk,
+            _ => return Err(Cause::UnsupportedTiffFrame),
+        };
+
+        ImageXObject {
+            width: Px(width),
+            height: Px(height),
+            color_space,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: false,
+            image_data: tiff_jpeg_strip(raw, decoder)?,
+            image_filter: Some(ImageFilter::DCT),
+            clipping_bbox: None,
+        }
+    } else if compression == CompressionMethod::OldJPEG as u32 {
+        // The pre-TIFF6 "old-style" JPEG layout doesn't store a self-contained interchange
+        // stream in StripOffsets/StripByteCounts the way the 1994 TechNote 2 scheme does, so
+        // tiff_jpeg_strip's splicing doesn't apply here. Decoding it would need the separate
+        // JpegInterchangeFormat tag, which the tiff crate doesn't expose; reject it explicitly
+        // rather than emit a malformed page.
+        return Err(Cause::UnsupportedTiffFrame);
+    } else {
+        let color_space = match decoder.colortype()? {
+            TiffColorType::Gray(8) => ColorSpace::Greyscale,
+            TiffColorType::RGB(8) => ColorSpace::Rgb,
+            TiffColorType::CMYK(8) => ColorSpace::Cmyk,
+            _ => return Err(Cause::UnsupportedTiffFrame),
+        };
+
+        let samples = match decoder.read_image()? {
+            DecodingResult::U8(samples) => samples,
+            _ => return Err(Cause::UnsupportedTiffFrame),
+        };
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let image_data = encoder
+            .write_all(&samples)
+            .and_then(|()| encoder.finish())
+            .map_err(Cause::ImageCompress)?;
+
+        ImageXObject {
+            width: Px(width),
+            height: Px(height),
+            color_space,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: false,
+            image_data,
+            image_filter: Some(ImageFilter::Flate),
+            clipping_bbox: None,
+        }
+    };
+
+    let (page_width, page_height, frame_dpi, translate_x, translate_y) =
+        page_geometry(page_layout, width, height, (frame_dpi, frame_dpi));
+
+    let (page, layer) = doc.add_page(page_width, page_height, "");
+
+    Image::from(image).add_to_layer(
+        doc.get_page(page).get_layer(layer),
+        translate_x,
+        translate_y,
+        None,
+        None,
+        None,
+        Some(frame_dpi.0),
+    );
+
+    Ok(())
+}
+
+/// Reads the `XResolution`/`ResolutionUnit` tags of the TIFF frame the decoder is currently
+/// positioned at, converting centimeters to inches. Returns `None` if no resolution is tagged.
+fn tiff_resolution(decoder: &mut TiffDecoder<Cursor<&Vec<u8>>>) -> Option<f64> {
+    let resolution = decoder.get_tag_rational(TiffTag::XResolution).ok()?;
+    let unit = decoder.get_tag_u32(TiffTag::ResolutionUnit).unwrap_or(2);
+
+    let dpi = resolution.to_f64();
+    Some(if unit == 3 { dpi * 2.54 } else { dpi })
+}
+
+/// Extracts the compressed strip bytes of a baseline-JPEG-compressed TIFF frame as a single
+/// interchange-format stream, splicing in the shared `JPEGTables` tag when the encoder split the
+/// quantization/Huffman tables out from the strip data.
+///
+/// `JPEGTables` holds its own abbreviated `SOI`...`EOI` stream containing only the table
+/// segments, and each strip holds its own `SOI`...`EOI` stream with the frame/scan data but no
+/// tables; naively concatenating them back to back yields a stream with a premature `EOI`
+/// followed by a second `SOI`, which most decoders (and PDF's `DCTDecode` filter) stop at. Per
+/// TIFF Technical Note 2, the fix is to drop both of those markers and splice the table segments
+/// in right after the first strip's own `SOI`.
+fn tiff_jpeg_strip(raw: &[u8], decoder: &mut TiffDecoder<Cursor<&Vec<u8>>>) -> Result<Vec<u8>, Cause> {
+    const SOI: [u8; 2] = [0xFF, 0xD8];
+    const EOI: [u8; 2] = [0xFF, 0xD9];
+
+    let offsets = decoder.get_tag_u32_vec(TiffTag::StripOffsets)?;
+    let byte_counts = decoder.get_tag_u32_vec(TiffTag::StripByteCounts)?;
+    let tables = decoder.get_tag_u8_vec(TiffTag::JPEGTables).ok();
+
+    let mut jpeg = Vec::new();
+    for (index, (offset, len)) in offsets.into_iter().zip(byte_counts).enumerate() {
+        let (offset, len) = (offset as usize, len as usize);
+        let strip = raw
+            .get(offset..offset + len)
+            .ok_or(Cause::UnsupportedTiffFrame)?;
+
+        if index == 0 {
+            let strip_body = strip.strip_prefix(&SOI).ok_or(Cause::UnsupportedTiffFrame)?;
+            jpeg.extend_from_slice(&SOI);
+            if let Some(tables) = &tables {
+                let tables_body = tables
+                    .strip_prefix(&SOI)
+                    .and_then(|t| t.strip_suffix(&EOI))
+                    .ok_or(Cause::UnsupportedTiffFrame)?;
+                jpeg.extend_from_slice(tables_body);
+            }
+            jpeg.extend_from_slice(strip_body);
+        } else {
+            jpeg.extend_from_slice(strip);
+        }
+    }
+
+    Ok(jpeg)
+}
+
+/// Embeds a JPEG 2000 image by extracting the bare codestream out of its `jp2c` box, reading
+/// just enough of the rest of the header to size the page and pick a color space.
+///
+/// PDF's `JPXDecode` filter expects the raw codestream, not the wrapping JP2 container (signature
+/// box, `ftyp`, `jp2h`, etc.) — embedding the whole file renders as garbage or nothing in readers
+/// that enforce this.
+fn add_jpeg2000_page(
+    image: Vec<u8>,
+    doc: &PdfDocumentReference,
+    dpi: f64,
+    page_layout: Option<PageLayout>,
+) -> Result<(), Cause> {
+    let (width, height) = jp2_dimensions(&image).ok_or(Cause::Jpeg2000Header)?;
+    let (color_space, bits_per_component) =
+        jp2_color_info(&image).ok_or(Cause::Jpeg2000Header)?;
+    let codestream = jp2_codestream(&image)
+        .ok_or(Cause::Jpeg2000Header)?
+        .to_vec();
+
+    let (page_width, page_height, dpi, translate_x, translate_y) =
+        page_geometry(page_layout, width, height, (dpi, dpi));
+
+    let (page, layer) = doc.add_page(page_width, page_height, "");
+
+    let image = Image::from(ImageXObject {
+        width: Px(width),
+        height: Px(height),
+        color_space,
+        bits_per_component,
+        interpolate: false,
+        image_data: codestream,
+        image_filter: Some(ImageFilter::Jpx),
+        clipping_bbox: None,
+    });
+
+    image.add_to_layer(
+        doc.get_page(page).get_layer(layer),
+        translate_x,
+        translate_y,
+        None,
+        None,
+        None,
+        Some(dpi.0),
+    );
+
+    Ok(())
+}
+
+/// Reads the body of a JP2 file's Image Header (`ihdr`) box: `HEIGHT(4) WIDTH(4) NC(2) BPC(1)
+/// C(1) UnkC(1) IPR(1)`, big-endian.
+///
+/// `ihdr` is nested inside the `jp2h` superbox rather than appearing at the top level, so unlike
+/// [`jp2_codestream`] this scans for the tag directly instead of walking box headers.
+fn jp2_ihdr(data: &[u8]) -> Option<&[u8]> {
+    let ihdr = data.windows(4).position(|w| w == b"ihdr")?;
+    data.get(ihdr + 4..ihdr + 18)
+}
+
+/// Reads the width and height out of a JP2 file's Image Header (`ihdr`) box, without decoding
+/// the codestream itself.
+fn jp2_dimensions(data: &[u8]) -> Option<(usize, usize)> {
+    let body = jp2_ihdr(data)?;
+
+    let height = u32::from_be_bytes(body[0..4].try_into().ok()?);
+    let width = u32::from_be_bytes(body[4..8].try_into().ok()?);
+
+    Some((width as usize, height as usize))
+}
+
+/// Derives a PDF color space and bit depth from a JP2 file's `ihdr` box `NC` (component count)
+/// and `BPC` (bits per component) fields, instead of assuming 8-bit RGB.
+fn jp2_color_info(data: &[u8]) -> Option<(ColorSpace, ColorBits)> {
+    let body = jp2_ihdr(data)?;
+
+    let components = u16::from_be_bytes(body[8..10].try_into().ok()?);
+    let color_space = match components {
+        1 => ColorSpace::Greyscale,
+        3 => ColorSpace::Rgb,
+        4 => ColorSpace::Cmyk,
+        _ => return None,
+    };
+
+    // The high bit flags signed samples, which doesn't affect the bit depth itself; 0xFF (BPC
+    // value 255) marks "bit depth varies per component", which has no single PDF equivalent.
+    let bpc = body[10];
+    if bpc == 0xFF {
+        return None;
+    }
+    let bits_per_component = match (bpc & 0x7F) + 1 {
+        1 => ColorBits::Bit1,
+        2 => ColorBits::Bit2,
+        4 => ColorBits::Bit4,
+        8 => ColorBits::Bit8,
+        16 => ColorBits::Bit16,
+        _ => return None,
+    };
+
+    Some((color_space, bits_per_component))
+}
+
+/// Extracts the bare codestream payload out of a JP2 file's `jp2c` box by walking the top-level
+/// box headers, handling both the 64-bit extended length form and the "extends to EOF" form
+/// (`LBox == 0`).
+fn jp2_codestream(data: &[u8]) -> Option<&[u8]> {
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let length = u32::from_be_bytes(data[offset..offset + 4].try_into().ok()?);
+        let box_type = &data[offset + 4..offset + 8];
+
+        let (header_len, total_len) = if length == 1 {
+            let ext_length = u64::from_be_bytes(data.get(offset + 8..offset + 16)?.try_into().ok()?);
+            (16, ext_length as usize)
+        } else if length == 0 {
+            (8, data.len() - offset)
+        } else {
+            (8, length as usize)
+        };
+
+        if total_len < header_len || offset + total_len > data.len() {
+            return None;
+        }
+
+        if box_type == b"jp2c" {
+            return data.get(offset + header_len..offset + total_len);
+        }
+
+        offset += total_len;
+    }
+    None
+}
+
 /// Creates a PDF file from the provided JPEG data.
 ///
 /// PDF data is written to `out`.
@@ -213,3 +1069,171 @@ pub fn create_pdf_from_jpegs(
         .set_dpi(dpi.unwrap_or(300.0))
         .create_pdf(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The Orientation::translate_x/y offsets are in raw image pixels, not mm, and have to be
+    // scaled by the per-image dpi a fixed page_layout just picked before they can be summed with
+    // the layout's own anchor offset in sum_mm. Orientation 6 (rotate90) exercises both: it
+    // contributes a Y translation (no X) in the un-rotated pixel space, while the Mm(150, 200)
+    // layout used here anchors the displayed (already-rotated) image with a nonzero X margin.
+    #[test]
+    fn page_layout_composes_with_exif_orientation_translation() {
+        let ori = Orientation {
+            value: 6,
+            width: 200,
+            height: 100,
+        };
+        let layout = PageLayout {
+            media_box: MediaBox::Mm(150.0, 200.0),
+            orientation: PageOrientation::Auto,
+            fit: FitPolicy::Contain,
+            anchor: Anchor::Center,
+        };
+
+        let (page_width, page_height, dpi, layout_translate_x, layout_translate_y) =
+            page_geometry(Some(layout), ori.display_width(), ori.display_height(), (300.0, 300.0));
+
+        // display_width/height (100, 200) fit a 150x200mm page at 1 image pixel per mm, centered
+        // with a 25mm margin on each side of the horizontal page axis.
+        assert!((page_width.0 - 150.0).abs() < 1e-9);
+        assert!((page_height.0 - 200.0).abs() < 1e-9);
+        assert!((dpi.0 - 25.4).abs() < 1e-9);
+        assert!((dpi.1 - 25.4).abs() < 1e-9);
+
+        let translate_x = sum_mm(
+            ori.translate_x().map(|px| Px(px).into_pt(dpi.0).into()),
+            layout_translate_x,
+        );
+        let translate_y = sum_mm(
+            ori.translate_y().map(|px| Px(px).into_pt(dpi.1).into()),
+            layout_translate_y,
+        );
+
+        // Orientation 6 contributes no X offset, so the total is purely the layout's anchor
+        // margin; it contributes a Y offset of the raw image width (200px == 200mm at 25.4 dpi),
+        // which lands on top of the layout's own (zero) Y margin.
+        assert_eq!(ori.rotate_cw(), Some(270.0));
+        assert!((translate_x.unwrap().0 - 25.0).abs() < 1e-9);
+        assert!((translate_y.unwrap().0 - 200.0).abs() < 1e-9);
+    }
+
+    fn ihdr_box(height: u32, width: u32, components: u16, bpc: u8) -> Vec<u8> {
+        let mut data = b"....ihdr".to_vec();
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&components.to_be_bytes());
+        data.push(bpc);
+        data.extend_from_slice(&[0, 0, 0]); // C, UnkC, IPR
+        data
+    }
+
+    #[test]
+    fn jp2_dimensions_reads_height_then_width() {
+        let data = ihdr_box(100, 200, 3, 7);
+        assert_eq!(jp2_dimensions(&data), Some((200, 100)));
+    }
+
+    #[test]
+    fn jp2_dimensions_is_none_without_an_ihdr_box() {
+        assert_eq!(jp2_dimensions(b"no header here"), None);
+    }
+
+    #[test]
+    fn jp2_dimensions_is_none_when_the_box_is_truncated() {
+        assert_eq!(jp2_dimensions(b"....ihdr\x00\x00"), None);
+    }
+
+    #[test]
+    fn jp2_color_info_maps_component_count_to_a_color_space() {
+        assert!(matches!(
+            jp2_color_info(&ihdr_box(1, 1, 1, 7)),
+            Some((ColorSpace::Greyscale, ColorBits::Bit8))
+        ));
+        assert!(matches!(
+            jp2_color_info(&ihdr_box(1, 1, 3, 7)),
+            Some((ColorSpace::Rgb, ColorBits::Bit8))
+        ));
+        assert!(matches!(
+            jp2_color_info(&ihdr_box(1, 1, 4, 7)),
+            Some((ColorSpace::Cmyk, ColorBits::Bit8))
+        ));
+    }
+
+    #[test]
+    fn jp2_color_info_reads_bit_depth_from_bpc() {
+        assert!(matches!(
+            jp2_color_info(&ihdr_box(1, 1, 1, 0)),
+            Some((ColorSpace::Greyscale, ColorBits::Bit1))
+        ));
+        assert!(matches!(
+            jp2_color_info(&ihdr_box(1, 1, 1, 15)),
+            Some((ColorSpace::Greyscale, ColorBits::Bit16))
+        ));
+    }
+
+    #[test]
+    fn jp2_color_info_is_none_for_unsupported_component_counts_or_variable_bit_depth() {
+        assert!(jp2_color_info(&ihdr_box(1, 1, 2, 7)).is_none());
+        assert!(jp2_color_info(&ihdr_box(1, 1, 3, 0xFF)).is_none());
+    }
+
+    #[test]
+    fn jp2_codestream_extracts_the_jp2c_box_payload() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&12u32.to_be_bytes());
+        data.extend_from_slice(b"jp2h");
+        data.extend_from_slice(b"....");
+
+        let jp2c_start = data.len();
+        data.extend_from_slice(&12u32.to_be_bytes());
+        data.extend_from_slice(b"jp2c");
+        data.extend_from_slice(b"CODE");
+
+        assert_eq!(jp2_codestream(&data), Some(b"CODE".as_slice()));
+        assert!(jp2c_start > 0); // the jp2c box isn't the first one in the file
+    }
+
+    #[test]
+    fn jp2_codestream_box_extends_to_end_of_file_when_its_length_is_zero() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(b"jp2c");
+        data.extend_from_slice(b"REST OF FILE");
+
+        assert_eq!(jp2_codestream(&data), Some(b"REST OF FILE".as_slice()));
+    }
+
+    #[test]
+    fn jp2_codestream_is_none_without_a_jp2c_box() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&12u32.to_be_bytes());
+        data.extend_from_slice(b"jp2h");
+        data.extend_from_slice(b"....");
+
+        assert_eq!(jp2_codestream(&data), None);
+    }
+
+    #[test]
+    fn parse_exif_datetime_reads_the_standard_format() {
+        // The offset itself is environment-dependent (local, falling back to UTC), so this only
+        // checks that the wall-clock fields are read verbatim from the tag.
+        let parsed = parse_exif_datetime("2023:06:15 09:30:45").unwrap();
+        assert_eq!(parsed.year(), 2023);
+        assert_eq!(parsed.month() as u8, 6);
+        assert_eq!(parsed.day(), 15);
+        assert_eq!(parsed.hour(), 9);
+        assert_eq!(parsed.minute(), 30);
+        assert_eq!(parsed.second(), 45);
+    }
+
+    #[test]
+    fn parse_exif_datetime_rejects_malformed_strings() {
+        assert_eq!(parse_exif_datetime(""), None);
+        assert_eq!(parse_exif_datetime("not a date"), None);
+        assert_eq!(parse_exif_datetime("2023:13:15 09:30:45"), None); // month 13
+        assert_eq!(parse_exif_datetime("2023:06:15"), None); // missing time
+    }
+}